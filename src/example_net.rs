@@ -1,41 +1,256 @@
 #[cfg(feature = "accelerate")]
 extern crate accelerate_src;
 
-use candle_core::{Result, Device, Tensor, DType, D};
+use candle_core::{Result, Device, Tensor, DType, Var, D};
 use candle_nn::{Module, Optimizer, Activation, Linear, VarBuilder, VarMap, linear};
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+use rand_distr::{Distribution, Normal, Uniform};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use crate::sequential_layers::{Sequential};
+use crate::gbdt::{GradientBoostedTrees, GBDTParams};
 
 // Plots
 use plotly::common::{Mode, color::NamedColor};
 use plotly::{Plot, Scatter};
 
 
-pub struct Dataset { 
+pub struct Dataset {
     train_data : Tensor,
     train_label : Tensor,
     test_data : Tensor,
     test_label : Tensor,
 }
 
+impl Dataset {
+    /// Training features as row-major `Vec<Vec<f32>>`, for learners (e.g.
+    /// `gbdt`) that work on plain slices rather than tensors.
+    pub fn train_features(&self) -> Result<Vec<Vec<f32>>> {
+        self.train_data.to_vec2::<f32>()
+    }
+
+    /// Training targets as a flat `Vec<f32>`, assuming a single-column
+    /// label tensor.
+    pub fn train_targets(&self) -> Result<Vec<f32>> {
+        Ok(self.train_label.to_vec2::<f32>()?.into_iter().map(|r| r[0]).collect())
+    }
+}
+
+/// Yields shuffled mini-batches of `(data, label)` rows over a full pass
+/// of `data`/`label`, reshuffling row order on every call to `epoch`. An
+/// explicit `seed` makes the per-epoch shuffles reproducible; without one
+/// the loader seeds itself from entropy.
+pub struct DataLoader {
+    data : Tensor,
+    label : Tensor,
+    batch_size : usize,
+    rng : StdRng,
+}
+
+impl DataLoader {
+    /// Fails if `batch_size` is zero, since `epoch` divides the row count
+    /// by it to size the batch vector.
+    pub fn new(data : Tensor, label : Tensor, batch_size : usize, seed : Option<u64>) -> Result<Self> {
+        if batch_size == 0 {
+            return Err(candle_core::Error::Msg("DataLoader: batch_size must be greater than zero".to_string()));
+        }
+        let rng = match seed {
+            Some(s) => StdRng::seed_from_u64(s),
+            None => StdRng::from_entropy(),
+        };
+        Ok(DataLoader { data, label, batch_size, rng })
+    }
+
+    /// Permutes the rows and splits them into `batch_size`-sized chunks
+    /// (the last chunk may be smaller), returning one `(data, label)`
+    /// pair per batch in the new order.
+    pub fn epoch(&mut self) -> Result<Vec<(Tensor, Tensor)>> {
+        let n = self.data.dim(0)?;
+        let mut indices : Vec<u32> = (0..n as u32).collect();
+        indices.shuffle(&mut self.rng);
+        let idx = Tensor::from_vec(indices, n, self.data.device())?;
+
+        let shuffled_data = self.data.index_select(&idx, 0)?;
+        let shuffled_label = self.label.index_select(&idx, 0)?;
+
+        let mut batches = Vec::with_capacity(n.div_ceil(self.batch_size));
+        let mut start = 0;
+        while start < n {
+            let len = self.batch_size.min(n - start);
+            batches.push((shuffled_data.narrow(0, start, len)?, shuffled_label.narrow(0, start, len)?));
+            start += len;
+        }
+        Ok(batches)
+    }
+}
+
+// Activations usable as the output stage of an ActivizedLayer. Wraps the
+// handful of candle_nn::Activation variants we actually use, plus
+// QuietSoftmax, which candle_nn has no equivalent for.
+#[derive(Clone, Copy, Debug)]
+pub enum LayerActivation {
+    Relu,
+    Sigmoid,
+    QuietSoftmax,
+    /// No squashing: passes the `Linear` output through unchanged. Use
+    /// this for a layer that must emit raw logits, e.g. right before
+    /// `CrossEntropyLoss`, which computes its own (stabilized) softmax
+    /// internally and expects unsquashed inputs.
+    Identity,
+}
+
+impl Module for LayerActivation {
+    fn forward(&self, x : &Tensor) -> Result<Tensor> {
+        match self {
+            LayerActivation::Relu => Activation::Relu.forward(x),
+            LayerActivation::Sigmoid => Activation::Sigmoid.forward(x),
+            LayerActivation::QuietSoftmax => quiet_softmax(x),
+            LayerActivation::Identity => Ok(x.clone()),
+        }
+    }
+}
+
+/// Softmax with an implicit zero-logit added to the denominator, so a row
+/// of all-small logits can attend to nothing instead of being forced to
+/// sum to one. Computed over the last dimension.
+///
+/// quiet_softmax(x)_i = exp(x_i - m) / (exp(-m) + sum_j exp(x_j - m))
+/// where m = max_j x_j, subtracted for numerical stability.
+pub fn quiet_softmax(x : &Tensor) -> Result<Tensor> {
+    let m = x.max_keepdim(D::Minus1)?;
+    let shifted = x.broadcast_sub(&m)?;
+    let numerator = shifted.exp()?;
+    let denom = (numerator.sum_keepdim(D::Minus1)? + m.neg()?.exp()?)?;
+    numerator.broadcast_div(&denom)
+}
+
+/// Weight-initialization strategies for `ActivizedLayer::new_seeded`.
+/// Kaiming uses a true ReLU gain of `sqrt(2)`; Xavier/Glorot reuses the
+/// same `sqrt(2)` constant, but there it is the numerator of the
+/// standard `sqrt(2 / (fan_in + fan_out))` formula, not a ReLU-specific
+/// gain. `Uniform` variants derive their bound from the corresponding
+/// normal's std so both flavours of a scheme agree on variance.
+#[derive(Clone, Copy, Debug)]
+pub enum Initializer {
+    KaimingUniform,
+    KaimingNormal,
+    XavierUniform,
+    XavierNormal,
+    Normal { std : f64 },
+    Uniform { lo : f64, hi : f64 },
+    Zeros,
+}
+
+impl Initializer {
+    fn sample(&self, fan_in : usize, fan_out : usize, count : usize, rng : &mut StdRng) -> Vec<f32> {
+        const DEFAULT_GAIN : f64 = std::f64::consts::SQRT_2;
+
+        match self {
+            Initializer::Zeros => vec![0_f32; count],
+            Initializer::Normal { std } => Self::sample_normal(0.0, *std, count, rng),
+            Initializer::Uniform { lo, hi } => Self::sample_uniform(*lo, *hi, count, rng),
+            Initializer::KaimingNormal => {
+                let std = DEFAULT_GAIN / (fan_in as f64).sqrt();
+                Self::sample_normal(0.0, std, count, rng)
+            }
+            Initializer::KaimingUniform => {
+                let std = DEFAULT_GAIN / (fan_in as f64).sqrt();
+                let bound = std * 3_f64.sqrt();
+                Self::sample_uniform(-bound, bound, count, rng)
+            }
+            Initializer::XavierNormal => {
+                let std = DEFAULT_GAIN / ((fan_in + fan_out) as f64).sqrt();
+                Self::sample_normal(0.0, std, count, rng)
+            }
+            Initializer::XavierUniform => {
+                let std = DEFAULT_GAIN / ((fan_in + fan_out) as f64).sqrt();
+                let bound = std * 3_f64.sqrt();
+                Self::sample_uniform(-bound, bound, count, rng)
+            }
+        }
+    }
+
+    fn sample_normal(mean : f64, std : f64, count : usize, rng : &mut StdRng) -> Vec<f32> {
+        let dist = Normal::new(mean, std).unwrap();
+        (0..count).map(|_| dist.sample(rng) as f32).collect()
+    }
+
+    fn sample_uniform(lo : f64, hi : f64, count : usize, rng : &mut StdRng) -> Vec<f32> {
+        let dist = Uniform::new(lo, hi);
+        (0..count).map(|_| dist.sample(rng) as f32).collect()
+    }
+}
+
 pub struct ActivizedLayer {
     layer : Linear,
-    activation : Activation
+    activation : LayerActivation
 }
 
 impl ActivizedLayer {
-    fn new(inp_size : usize, 
-            out_size : usize, 
-            activation : Activation, 
-            vs : &VarBuilder) -> Result<ActivizedLayer> { 
+    fn new(inp_size : usize,
+            out_size : usize,
+            activation : LayerActivation,
+            vs : &VarBuilder) -> Result<ActivizedLayer> {
 
-        let layer = linear(inp_size, out_size, 
+        let layer = linear(inp_size, out_size,
                         vs.pp(format!("i{inp_size}o{out_size}")))?;
         Ok(ActivizedLayer {
             layer : layer ,
             activation : activation
         })
     }
+
+    /// Same as `new`, but samples the weight and bias from an explicit
+    /// `Initializer` instead of candle's default linear init, seeded so
+    /// the layer is reproducible. Unlike `new`, whose `i{inp}o{out}`
+    /// prefix ties together same-shaped layers on purpose, two
+    /// `new_seeded` layers must never share a varmap key: a shared key
+    /// would silently evict the first layer's `Var` from
+    /// `varmap.all_vars()`, leaving it untrainable. Each call therefore
+    /// gets its own prefix from a process-wide monotonic counter.
+    pub fn new_seeded(inp_size : usize,
+            out_size : usize,
+            activation : LayerActivation,
+            init : Initializer,
+            seed : u64,
+            varmap : &VarMap,
+            dev : &Device) -> Result<ActivizedLayer> {
+
+        static NEXT_SEEDED_LAYER_ID : AtomicUsize = AtomicUsize::new(0);
+        let layer_id = NEXT_SEEDED_LAYER_ID.fetch_add(1, Ordering::Relaxed);
+        let prefix = format!("seeded{layer_id}_i{inp_size}o{out_size}");
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let weight_data = init.sample(inp_size, out_size, out_size * inp_size, &mut rng);
+        let bias_data = init.sample(inp_size, out_size, out_size, &mut rng);
+        let weight = Var::from_tensor(&Tensor::from_vec(weight_data, (out_size, inp_size), dev)?)?;
+        let bias = Var::from_tensor(&Tensor::from_vec(bias_data, out_size, dev)?)?;
+
+        let mut data = varmap.data().lock().unwrap();
+        data.insert(format!("{prefix}.weight"), weight.clone());
+        data.insert(format!("{prefix}.bias"), bias.clone());
+        drop(data);
+
+        let layer = Linear::new(weight.as_tensor().clone(), Some(bias.as_tensor().clone()));
+        Ok(ActivizedLayer {
+            layer : layer,
+            activation : activation
+        })
+    }
+
+    pub fn weight(&self) -> &Tensor {
+        self.layer.weight()
+    }
+
+    pub fn bias(&self) -> Option<&Tensor> {
+        self.layer.bias()
+    }
+
+    /// Overwrites the layer's weight/bias in place, e.g. after loading a
+    /// checkpoint. The activation is left untouched.
+    pub fn set_weights(&mut self, weight : Tensor, bias : Option<Tensor>) {
+        self.layer = Linear::new(weight, bias);
+    }
 }
 
 impl Module for ActivizedLayer {
@@ -59,36 +274,73 @@ impl Loss for MSELoss {
     }
 }
 
-pub fn train(m: Dataset, 
-                    model: impl Module,
-                    mut optimizer: impl Optimizer, 
+/// Softmax cross-entropy against integer class targets, i.e. the usual
+/// classification loss: -log(softmax(input)[target]) averaged over the
+/// batch. `target` holds one class index per row (U32) and `input` holds
+/// the raw logits; the softmax is never materialized explicitly, instead
+/// the log-sum-exp trick keeps it numerically stable for large logits.
+#[derive(Clone, Debug)]
+pub struct CrossEntropyLoss;
+
+impl Loss for CrossEntropyLoss {
+    fn loss(self, input : &Tensor, target : &Tensor) -> Result<Tensor> {
+        let m = input.max_keepdim(D::Minus1)?;
+        let shifted = input.broadcast_sub(&m)?;
+        let log_sum_exp = shifted.exp()?.sum_keepdim(D::Minus1)?.log()?;
+        let log_probs = shifted.broadcast_sub(&log_sum_exp)?;
+        let picked = log_probs.gather(&target.unsqueeze(D::Minus1)?, D::Minus1)?;
+        picked.neg()?.mean_all()
+    }
+}
+
+/// Trains `model` in place and hands back both the model and the
+/// `VarMap` backing its weights, so the caller can checkpoint them with
+/// `Sequential::save_weights` afterwards. `model` stays a concrete type
+/// (rather than `impl Module`) so the returned value keeps whatever
+/// checkpointing/inspection methods it had before training.
+pub fn train<M: Module>(m: Dataset,
+                    model: M,
+                    varmap: VarMap,
+                    mut optimizer: impl Optimizer,
                     loss : impl Loss + Clone,
-                    epochs : usize, 
-                    dev: &Device) -> Result<impl Module> {
+                    epochs : usize,
+                    batch_size : usize,
+                    seed : Option<u64>,
+                    dev: &Device) -> Result<(M, VarMap)> {
     // Export from dataset
-    let train_results = m.train_data.to_device(dev)?;
-    let train_votes   = m.train_label.to_device(dev)?;
+    let train_votes   = m.train_data.to_device(dev)?;
+    let train_results = m.train_label.to_device(dev)?;
     let test_votes    = m.test_data.to_device(dev)?;
     let test_results  = m.test_label.to_device(dev)?;
     // accuracy
     let mut final_accuracy: f32 = 0.0;
-    
+
+    let mut loader = DataLoader::new(train_votes, train_results, batch_size, seed)?;
+
     // Training loop
     for epoch in 1..epochs + 1 {
-        // Inference
-        let out = model.forward(&train_votes)?;
-        let loss_train = loss.clone().loss(&out, &train_results)?;
-        // Optimize 
-        optimizer.backward_step(&loss_train)?;
+        let batches = loader.epoch()?;
+        let num_batches = batches.len();
+
+        // Inference, one mini-batch at a time
+        let mut train_loss_sum = 0_f32;
+        for (batch_votes, batch_results) in batches.iter() {
+            let out = model.forward(batch_votes)?;
+            let loss_train = loss.clone().loss(&out, batch_results)?;
+            // Optimize
+            optimizer.backward_step(&loss_train)?;
+            train_loss_sum += loss_train.to_scalar::<f32>()?;
+        }
+        let mean_train_loss = train_loss_sum / num_batches as f32;
 
         let test_forward = model.forward(&test_votes)?;
         let loss_test = loss.clone().loss(&test_forward, &test_results)?;
         println!("Epoch: {epoch:3} Train loss: {:8.5} Test loss: {:8.5}",
-                 loss_train.to_scalar::<f32>()?,
+                 mean_train_loss,
                  loss_test.to_scalar::<f32>()?
-        ); 
+        );
     }
-    Ok(model)
+    Ok((model, varmap))
 }
 
 pub fn SIN_DATASET_EXAMPLE() -> Result<()> {
@@ -102,18 +354,40 @@ pub fn SIN_DATASET_EXAMPLE() -> Result<()> {
                     train_label : train_label, 
                     test_data : test_data.clone(),
                     test_label : test_label.clone() };
+
+    // Classical baseline alongside the neural net: gradient-boosted
+    // regression trees on the same data, plus the feature importance
+    // they give for free.
+    let gbdt_model = GradientBoostedTrees::fit(&dt, &GBDTParams::default())?;
+    let gbdt_predictions = gbdt_model.predict(&test_data)?;
+    let gbdt_test_loss = (&gbdt_predictions - &test_label)?.sqr()?.mean_all()?;
+    println!("GBDT baseline: test MSE {:8.5}, feature importance {:?}",
+             gbdt_test_loss.to_scalar::<f32>()?,
+             gbdt_model.feature_importance());
+
     let varmap = VarMap::new();
     let vs     = VarBuilder::from_varmap(&varmap, DType::F32, &dev);
-    let unpk1 = ActivizedLayer::new(1, 20, Activation::Relu, &vs)?;
-    let unpk2 = ActivizedLayer::new(20, 40, Activation::Relu, &vs)?;
-    let unpk3 = ActivizedLayer::new(40, 1, Activation::Sigmoid, &vs)?;
-    let seq_net = Sequential::new(vec!(unpk1, 
-                    unpk2, 
+    let unpk1 = ActivizedLayer::new_seeded(1, 20, LayerActivation::Relu, Initializer::KaimingUniform, 42, &varmap, &dev)?;
+    let unpk2 = ActivizedLayer::new(20, 40, LayerActivation::Relu, &vs)?;
+    let unpk3 = ActivizedLayer::new(40, 1, LayerActivation::Sigmoid, &vs)?;
+    let seq_net = Sequential::new(vec!(unpk1,
+                    unpk2,
                     unpk3))?;
-    let mut optim = candle_nn::SGD::new(varmap.all_vars(), 0.05)?;
+    let optim = candle_nn::SGD::new(varmap.all_vars(), 0.05)?;
     let loss = MSELoss {};
-    let trained_model = train(dt, seq_net, optim, loss, 100_usize, &dev)?;
-    
+    let (trained_model, _varmap) = train(dt, seq_net, varmap, optim, loss, 100_usize, 16_usize, Some(42), &dev)?;
+    trained_model.save_weights("sin_dataset_example.safetensors")?;
+
+    // Prove the checkpoint round-trips: reload it into a freshly built
+    // network of matching shape and use that one for inference below.
+    let reload_varmap = VarMap::new();
+    let reload_vs = VarBuilder::from_varmap(&reload_varmap, DType::F32, &dev);
+    let reload1 = ActivizedLayer::new(1, 20, LayerActivation::Relu, &reload_vs)?;
+    let reload2 = ActivizedLayer::new(20, 40, LayerActivation::Relu, &reload_vs)?;
+    let reload3 = ActivizedLayer::new(40, 1, LayerActivation::Sigmoid, &reload_vs)?;
+    let mut trained_model = Sequential::new(vec!(reload1, reload2, reload3))?;
+    trained_model.load_weights("sin_dataset_example.safetensors", &dev)?;
+
     let mut sorted_vec = test_data.clone().to_vec2::<f32>()?
                         .into_iter()
                         .map(|r| r[0])
@@ -126,6 +400,49 @@ pub fn SIN_DATASET_EXAMPLE() -> Result<()> {
     Ok(())
 }
 
+/// Classifies whether `sin(x)` is non-negative (class 1) or negative
+/// (class 0) from `x` alone. Exercises the classification path added
+/// alongside `SIN_DATASET_EXAMPLE`'s regression one: a `QuietSoftmax`
+/// hidden layer plus `CrossEntropyLoss`, and a direct call to the
+/// standalone `quiet_softmax` function to inspect class probabilities.
+pub fn CLASSIFICATION_DATASET_EXAMPLE() -> Result<()> {
+    let dev = Device::cuda_if_available(0)?;
+    let train_data  = Tensor::randn(0_f32, 6_f32, (200, 1), &dev)?;
+    let test_data   = Tensor::randn(2_f32, 7_f32, (100, 1), &dev)?;
+    let train_label = sign_of_sin_labels(&train_data)?;
+    let test_label  = sign_of_sin_labels(&test_data)?;
+
+    let dt = Dataset { train_data : train_data,
+                    train_label : train_label,
+                    test_data : test_data.clone(),
+                    test_label : test_label };
+    let varmap = VarMap::new();
+    let vs     = VarBuilder::from_varmap(&varmap, DType::F32, &dev);
+    let unpk1 = ActivizedLayer::new(1, 20, LayerActivation::Relu, &vs)?;
+    let unpk2 = ActivizedLayer::new(20, 20, LayerActivation::QuietSoftmax, &vs)?;
+    let unpk3 = ActivizedLayer::new(20, 2, LayerActivation::Identity, &vs)?;
+    let seq_net = Sequential::new(vec!(unpk1, unpk2, unpk3))?;
+    let optim = candle_nn::SGD::new(varmap.all_vars(), 0.05)?;
+    let loss = CrossEntropyLoss {};
+    let (trained_model, _varmap) = train(dt, seq_net, varmap, optim, loss, 50_usize, 16_usize, Some(42), &dev)?;
+
+    let probs = quiet_softmax(&trained_model.forward(&test_data)?)?;
+    println!("Quiet-softmax class probabilities for first test row: {:?}",
+             probs.to_vec2::<f32>()?[0]);
+    Ok(())
+}
+
+/// `1` where `sin(x) >= 0`, `0` otherwise, as the `U32` class targets
+/// `CrossEntropyLoss` expects.
+fn sign_of_sin_labels(data : &Tensor) -> Result<Tensor> {
+    let labels : Vec<u32> = data.sin()?.to_vec2::<f32>()?
+        .into_iter()
+        .map(|r| if r[0] >= 0.0 { 1_u32 } else { 0_u32 })
+        .collect();
+    let n = labels.len();
+    Tensor::from_vec(labels, n, data.device())
+}
+
 fn draw_plot(x : &Tensor, y : &Tensor, z : &Tensor) -> Result<()> {
     // Define some sample data
     let x_values  : Vec<f32> = x.to_vec2::<f32>()?
@@ -160,3 +477,94 @@ fn draw_plot(x : &Tensor, y : &Tensor, z : &Tensor) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cross_entropy_loss_matches_hand_computed_value() -> Result<()> {
+        let dev = Device::Cpu;
+        let input = Tensor::new(&[[1f32, 2., 3.], [1., 2., 3.]], &dev)?;
+        let target = Tensor::new(&[2u32, 0u32], &dev)?;
+        let loss = CrossEntropyLoss.loss(&input, &target)?;
+
+        // -log_softmax(row)[target], averaged over the batch.
+        let expected = 1.407_6059_f32;
+        assert!((loss.to_scalar::<f32>()? - expected).abs() < 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_seeded_is_reproducible_and_matches_shape() -> Result<()> {
+        let dev = Device::Cpu;
+        let varmap1 = VarMap::new();
+        let layer1 = ActivizedLayer::new_seeded(4, 8, LayerActivation::Relu, Initializer::KaimingUniform, 7, &varmap1, &dev)?;
+        let varmap2 = VarMap::new();
+        let layer2 = ActivizedLayer::new_seeded(4, 8, LayerActivation::Relu, Initializer::KaimingUniform, 7, &varmap2, &dev)?;
+
+        assert_eq!(layer1.weight().dims(), &[8, 4]);
+        assert_eq!(layer1.bias().unwrap().dims(), &[8]);
+
+        // Same seed + same init scheme must reproduce identical weights.
+        let diff = (layer1.weight() - layer2.weight())?.abs()?.sum_all()?.to_scalar::<f32>()?;
+        assert_eq!(diff, 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_seeded_stacked_same_shape_layers_stay_independently_optimizable() -> Result<()> {
+        let dev = Device::Cpu;
+        let varmap = VarMap::new();
+        let layer1 = ActivizedLayer::new_seeded(64, 64, LayerActivation::Relu, Initializer::KaimingUniform, 1, &varmap, &dev)?;
+        let layer2 = ActivizedLayer::new_seeded(64, 64, LayerActivation::Relu, Initializer::KaimingUniform, 2, &varmap, &dev)?;
+
+        // Different seeds must not collide onto the same varmap entry and
+        // evict each other's `Var`.
+        let diff = (layer1.weight() - layer2.weight())?.abs()?.sum_all()?.to_scalar::<f32>()?;
+        assert!(diff > 0.0);
+
+        // Both layers' vars must still be present and distinct in the
+        // varmap, not just in the `ActivizedLayer` structs.
+        let all_vars = varmap.all_vars();
+        assert_eq!(all_vars.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dataloader_epoch_batches_cover_all_rows_and_respect_batch_size() -> Result<()> {
+        let dev = Device::Cpu;
+        let n = 10;
+        let data = Tensor::arange(0_f32, n as f32, &dev)?.reshape((n, 1))?;
+        let label = data.clone();
+        let mut loader = DataLoader::new(data, label, 3, Some(42))?;
+
+        let batches = loader.epoch()?;
+        let sizes : Vec<usize> = batches.iter()
+            .map(|(d, _)| d.dim(0))
+            .collect::<Result<_>>()?;
+        assert_eq!(sizes, vec![3, 3, 3, 1]);
+        assert_eq!(sizes.iter().sum::<usize>(), n);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dataloader_same_seed_reproduces_batch_order() -> Result<()> {
+        let dev = Device::Cpu;
+        let n = 8;
+        let data = Tensor::arange(0_f32, n as f32, &dev)?.reshape((n, 1))?;
+        let label = data.clone();
+
+        let mut loader1 = DataLoader::new(data.clone(), label.clone(), 4, Some(7))?;
+        let mut loader2 = DataLoader::new(data, label, 4, Some(7))?;
+
+        let batches1 = loader1.epoch()?;
+        let batches2 = loader2.epoch()?;
+
+        for ((d1, _), (d2, _)) in batches1.iter().zip(batches2.iter()) {
+            let diff = (d1 - d2)?.abs()?.sum_all()?.to_scalar::<f32>()?;
+            assert_eq!(diff, 0.0);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use candle_core::{Result, Tensor};
+
+use crate::example_net::Dataset;
+
+/// Hyperparameters for `GradientBoostedTrees::fit`.
+#[derive(Clone, Debug)]
+pub struct GBDTParams {
+    pub num_trees : usize,
+    pub learning_rate : f32,
+    pub max_depth : usize,
+    pub min_samples_leaf : usize,
+}
+
+impl Default for GBDTParams {
+    fn default() -> Self {
+        GBDTParams {
+            num_trees : 100,
+            learning_rate : 0.1,
+            max_depth : 3,
+            min_samples_leaf : 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum TreeNode {
+    Leaf { value : f32 },
+    Split {
+        feature : usize,
+        threshold : f32,
+        left : Box<TreeNode>,
+        right : Box<TreeNode>,
+    },
+}
+
+/// A single CART regression tree, greedily split on squared-error
+/// reduction. Leaves store the mean target of the samples routed to
+/// them.
+#[derive(Clone, Debug)]
+pub struct RegressionTree {
+    root : TreeNode,
+}
+
+impl RegressionTree {
+    pub fn fit(features : &[Vec<f32>], targets : &[f32], max_depth : usize, min_samples_leaf : usize) -> Self {
+        let (tree, _importance) = Self::fit_with_importance(features, targets, max_depth, min_samples_leaf);
+        tree
+    }
+
+    /// Same as `fit`, but also returns the total squared-error reduction
+    /// contributed by each feature across every split in the tree, for
+    /// feature-importance accounting.
+    pub fn fit_with_importance(features : &[Vec<f32>], targets : &[f32], max_depth : usize, min_samples_leaf : usize)
+        -> (Self, HashMap<usize, f32>) {
+        // Clamped to at least 1: a 0 here would let `best_split` try
+        // split point 0, whose left child is empty, so indexing its last
+        // sample (`left.len() - 1`) underflows.
+        let min_samples_leaf = min_samples_leaf.max(1);
+        let indices : Vec<usize> = (0..targets.len()).collect();
+        let (root, importance) = Self::build(features, targets, &indices, max_depth, min_samples_leaf);
+        (RegressionTree { root }, importance)
+    }
+
+    fn build(features : &[Vec<f32>], targets : &[f32], indices : &[usize], depth : usize, min_samples_leaf : usize)
+        -> (TreeNode, HashMap<usize, f32>) {
+        let mean = Self::mean(targets, indices);
+
+        if depth == 0 || indices.len() < 2 * min_samples_leaf {
+            return (TreeNode::Leaf { value : mean }, HashMap::new());
+        }
+
+        let parent_sse = Self::sse(targets, indices);
+        match Self::best_split(features, targets, indices, min_samples_leaf) {
+            None => (TreeNode::Leaf { value : mean }, HashMap::new()),
+            Some((feature, threshold, left_idx, right_idx, child_error)) => {
+                let (left, left_importance) = Self::build(features, targets, &left_idx, depth - 1, min_samples_leaf);
+                let (right, right_importance) = Self::build(features, targets, &right_idx, depth - 1, min_samples_leaf);
+
+                let mut importance = left_importance;
+                for (k, v) in right_importance {
+                    *importance.entry(k).or_insert(0.0) += v;
+                }
+                *importance.entry(feature).or_insert(0.0) += parent_sse - child_error;
+
+                (TreeNode::Split {
+                    feature,
+                    threshold,
+                    left : Box::new(left),
+                    right : Box::new(right),
+                }, importance)
+            }
+        }
+    }
+
+    fn mean(targets : &[f32], indices : &[usize]) -> f32 {
+        indices.iter().map(|&i| targets[i]).sum::<f32>() / indices.len() as f32
+    }
+
+    fn sse(targets : &[f32], indices : &[usize]) -> f32 {
+        let mean = Self::mean(targets, indices);
+        indices.iter().map(|&i| (targets[i] - mean).powi(2)).sum()
+    }
+
+    // Scans every feature, sorting samples by it, and keeps the
+    // threshold minimizing total child squared error. Splits that don't
+    // actually separate two distinct feature values, or would leave a
+    // child under `min_samples_leaf`, are skipped.
+    fn best_split(features : &[Vec<f32>], targets : &[f32], indices : &[usize], min_samples_leaf : usize)
+        -> Option<(usize, f32, Vec<usize>, Vec<usize>, f32)> {
+        let num_features = features[indices[0]].len();
+        let mut best : Option<(usize, f32, Vec<usize>, Vec<usize>, f32)> = None;
+
+        for feature in 0..num_features {
+            let mut sorted = indices.to_vec();
+            sorted.sort_by(|&a, &b| features[a][feature].partial_cmp(&features[b][feature]).unwrap());
+
+            for split in min_samples_leaf..=(sorted.len() - min_samples_leaf) {
+                let (left, right) = sorted.split_at(split);
+                if left.len() < min_samples_leaf || right.len() < min_samples_leaf {
+                    continue;
+                }
+                let threshold = features[left[left.len() - 1]][feature];
+                if features[right[0]][feature] == threshold {
+                    continue;
+                }
+
+                let error = Self::sse(targets, left) + Self::sse(targets, right);
+                let is_better = best.as_ref().map_or(true, |(_, _, _, _, best_error)| error < *best_error);
+                if is_better {
+                    best = Some((feature, threshold, left.to_vec(), right.to_vec(), error));
+                }
+            }
+        }
+
+        best
+    }
+
+    pub fn predict_one(&self, row : &[f32]) -> f32 {
+        let mut node = &self.root;
+        loop {
+            match node {
+                TreeNode::Leaf { value } => return *value,
+                TreeNode::Split { feature, threshold, left, right } => {
+                    node = if row[*feature] <= *threshold { left } else { right };
+                }
+            }
+        }
+    }
+}
+
+/// Gradient-boosted regression trees: starts from the target mean and
+/// fits each successive `RegressionTree` to the current residuals
+/// `y_i - y_hat_i` (the negative gradient of squared-error loss),
+/// nudging predictions by `learning_rate * tree.predict_one(x_i)` per
+/// round. A classical, non-neural counterpart to the candle-based models
+/// elsewhere in this crate.
+#[derive(Clone, Debug)]
+pub struct GradientBoostedTrees {
+    base_prediction : f32,
+    learning_rate : f32,
+    trees : Vec<RegressionTree>,
+    feature_importance : HashMap<usize, f32>,
+}
+
+impl GradientBoostedTrees {
+    pub fn fit(data : &Dataset, params : &GBDTParams) -> Result<Self> {
+        let features = data.train_features()?;
+        let targets = data.train_targets()?;
+
+        let base_prediction = targets.iter().sum::<f32>() / targets.len() as f32;
+        let mut predictions = vec![base_prediction; targets.len()];
+        let mut trees = Vec::with_capacity(params.num_trees);
+        let mut feature_importance : HashMap<usize, f32> = HashMap::new();
+
+        for _round in 0..params.num_trees {
+            let residuals : Vec<f32> = targets.iter().zip(predictions.iter())
+                                            .map(|(y, y_hat)| y - y_hat)
+                                            .collect();
+            let (tree, importance) = RegressionTree::fit_with_importance(
+                &features, &residuals, params.max_depth, params.min_samples_leaf);
+
+            for (row, prediction) in features.iter().zip(predictions.iter_mut()) {
+                *prediction += params.learning_rate * tree.predict_one(row);
+            }
+            for (feature, reduction) in importance {
+                *feature_importance.entry(feature).or_insert(0.0) += reduction;
+            }
+            trees.push(tree);
+        }
+
+        Ok(GradientBoostedTrees {
+            base_prediction,
+            learning_rate : params.learning_rate,
+            trees,
+            feature_importance,
+        })
+    }
+
+    pub fn predict(&self, x : &Tensor) -> Result<Tensor> {
+        let features = x.to_vec2::<f32>()?;
+        let predictions : Vec<f32> = features.iter()
+            .map(|row| {
+                let boost : f32 = self.trees.iter().map(|tree| tree.predict_one(row)).sum();
+                self.base_prediction + self.learning_rate * boost
+            })
+            .collect();
+
+        let num_rows = predictions.len();
+        Tensor::from_vec(predictions, (num_rows, 1), x.device())
+    }
+
+    /// Total squared-error reduction contributed by each feature index,
+    /// accumulated over every split in every tree of the ensemble.
+    pub fn feature_importance(&self) -> &HashMap<usize, f32> {
+        &self.feature_importance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regression_tree_fits_toy_step_function() {
+        let features = vec![
+            vec![0.1], vec![0.2], vec![0.3],
+            vec![0.8], vec![0.9], vec![0.95],
+        ];
+        let targets = vec![1.0, 1.0, 1.0, 5.0, 5.0, 5.0];
+
+        let tree = RegressionTree::fit(&features, &targets, 3, 1);
+
+        for (row, &target) in features.iter().zip(targets.iter()) {
+            assert!((tree.predict_one(row) - target).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_best_split_tolerates_zero_min_samples_leaf() {
+        let features = vec![vec![0.0], vec![1.0]];
+        let targets = vec![0.0, 1.0];
+
+        // Previously panicked: min_samples_leaf == 0 let `best_split` try
+        // an empty left child, underflowing `left.len() - 1`.
+        let tree = RegressionTree::fit(&features, &targets, 2, 0);
+        assert!((tree.predict_one(&[0.0]) - 0.0).abs() < 1e-4);
+        assert!((tree.predict_one(&[1.0]) - 1.0).abs() < 1e-4);
+    }
+}
@@ -2,8 +2,17 @@ use candle_core::{Result};
 
 mod sequential_layers;
 mod example_net;
-use example_net::{SIN_DATASET_EXAMPLE};
+mod gbdt;
+mod rabin_karp;
+use example_net::{SIN_DATASET_EXAMPLE, CLASSIFICATION_DATASET_EXAMPLE};
+use rabin_karp::rabin_karp;
 
 fn main() -> Result<()> {
-    SIN_DATASET_EXAMPLE()
+    let text = "ACACACGACGATG";
+    let segments = ["ACG", "ACGA"];
+    let matches = rabin_karp(text, &segments, 4_usize, 127_usize);
+    println!("Rabin-Karp matches of {segments:?} in {text:?}: {matches:?}");
+
+    SIN_DATASET_EXAMPLE()?;
+    CLASSIFICATION_DATASET_EXAMPLE()
 }
@@ -1,60 +1,78 @@
-
+use std::collections::HashMap;
 
 ///
-/// Given a long string and some fragments, returns the 
+/// Given a long string and some fragments, returns the
 /// indexes at which the fragments can be found in the original
-/// string.
+/// string. Fragments may have different lengths: patterns are grouped
+/// by length and each distinct length gets its own rolling hash pass
+/// over the text, so this is really `O(text_length * distinct_lengths)`
+/// rather than one pass per pattern. Matching is done on bytes, not
+/// `char`s, since the rolling hash is a positional weighted sum and has
+/// no notion of multi-byte UTF-8 sequences.
 ///
 /// long_text : a long string containing the segments
-/// segments : a ref to a list of strings.
-/// b : base, i.e. number of symbols used in the text. 
+/// segments : a ref to a list of strings, possibly of differing lengths.
+/// b : base, i.e. number of symbols used in the text.
 /// q : prime for the hash base
-pub fn rabin_karp(long_text : &str, 
-                  segments : &[&str], 
-                  b : usize, 
+///
+/// Returns one `Vec<usize>` of match positions per entry of `segments`,
+/// in the same order. Patterns longer than `long_text` simply get no
+/// matches; an empty `segments` list returns an empty `Vec`.
+pub fn rabin_karp(long_text : &str,
+                  segments : &[&str],
+                  b : usize,
                   q : usize) -> Vec<Vec<usize>> {
-    // general infos
-    let text_length     : usize  = long_text.len();
-    let num_segments    : usize  = segments.len();
-    let segment_length  : usize  = segments[0].len();
-
-    // compute b ^ (segment_len - 1) mod q
-    let b_star : usize = (1.. segment_length).fold(b, |acc, _x| acc * b % q);
-
-    // Computes the hashes of the known segments
-    let segment_hashes : Vec<usize> = segments.iter()
-                                           .map(|v| rolling_hash(v, b, q))
-                                           .collect();
-
-    // mut variables of the code.
-    let mut positions : Vec<Vec<usize>> = vec![Vec::new(); num_segments]; 
-    let mut hash_cur_pattern : usize = rolling_hash(&long_text[..segment_length], b, q);
-    let mut cur_string : String = String::from(&long_text[..segment_length]);
-
-    for (i, char) in long_text.char_indices()
-                              .skip(segment_length)
-                              .take(text_length - segment_length) {
-
-        // Search for pattern by hashing.
-        for j in 0.. num_segments {
-            // here short circuits prevents from wasting a double-if
-            // append the position only if the hash and equality are satisfied.
-           if hash_cur_pattern == segment_hashes[j] &&  
-                cur_string.as_str() == segments[j] {
-                positions[j].push(i-segment_length);
-            }
+    if segments.is_empty() {
+        return Vec::new();
+    }
+
+    let text : &[u8] = long_text.as_bytes();
+    let text_length : usize = text.len();
+    let mut positions : Vec<Vec<usize>> = vec![Vec::new(); segments.len()];
+
+    // Group pattern indices by length: each length needs its own rolling
+    // hash window, but all patterns sharing a length can be checked in
+    // the same pass over the text.
+    let mut by_length : HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, segment) in segments.iter().enumerate() {
+        by_length.entry(segment.len()).or_default().push(i);
+    }
+
+    for (len, pattern_indices) in by_length {
+        // Patterns longer than the text (or empty) can never match.
+        if len == 0 || len > text_length {
+            continue;
         }
 
-        // updates the current string to check
-        let s = cur_string.remove(0);
-        cur_string.push(char);
+        // compute b ^ len mod q: the weight of the outgoing byte once the
+        // window hash has been multiplied by b in the slide below
+        let b_star : usize = (1.. len).fold(b, |acc, _x| acc * b % q);
+        let pattern_hashes : Vec<usize> = pattern_indices.iter()
+                                        .map(|&i| rolling_hash(segments[i], b, q))
+                                        .collect();
+
+        let mut hash_cur_window : usize = bytes_hash(&text[..len], b, q);
+
+        for start in 0..= (text_length - len) {
+            if start > 0 {
+                // slide the window by one byte: drop text[start-1], add text[start+len-1]
+                let si  = text[start - 1] as usize;
+                let sim = text[start + len - 1] as usize;
+                hash_cur_window  = (b * hash_cur_window + sim) % q;
+                hash_cur_window  = (hash_cur_window + q - (si * b_star % q)) % q;
+            }
 
-        // Updates the rolling hash
-        let si  = s as usize;
-        let sim = char as usize;
-        hash_cur_pattern  = (b * hash_cur_pattern + sim) % q;
-        hash_cur_pattern  = (hash_cur_pattern + q - (si * b_star % q)) % q;
-   }
+            // Search for pattern by hashing.
+            for (k, &j) in pattern_indices.iter().enumerate() {
+                // here short circuits prevents from wasting a double-if
+                // append the position only if the hash and equality are satisfied.
+                if hash_cur_window == pattern_hashes[k] &&
+                    &text[start..start + len] == segments[j].as_bytes() {
+                    positions[j].push(start);
+                }
+            }
+        }
+    }
 
     positions
 }
@@ -65,8 +83,12 @@ pub fn rabin_karp(long_text : &str,
 /// q : modulo of the hash (so the total size of the hashed space).
 pub fn rolling_hash(string_to_hash : &str, b : usize, q : usize) -> usize
 {
-    string_to_hash.chars()
-                  .fold(0_usize, |acc, x| (acc * b + (x as usize)) % q) 
+    bytes_hash(string_to_hash.as_bytes(), b, q)
+}
+
+fn bytes_hash(bytes : &[u8], b : usize, q : usize) -> usize {
+    bytes.iter()
+         .fold(0_usize, |acc, &x| (acc * b + (x as usize)) % q)
 }
 
 
@@ -90,7 +112,7 @@ mod tests {
 
     #[test]
     fn rabin_karp_test() {
-        let seg1 = String::from("ACG"); 
+        let seg1 = String::from("ACG");
         let segments = [seg1.as_str()];
         let positions = rabin_karp("ACACACGACGATG", &segments, 4_usize, 127_usize);
         println!("{:?}", positions);
@@ -100,4 +122,37 @@ mod tests {
         v[0].push(7_usize);
         assert_eq!(positions, v);
     }
-} 
+
+    #[test]
+    fn rabin_karp_multi_length_patterns_test() {
+        let seg1 = String::from("AC");
+        let seg2 = String::from("ACGA");
+        let segments = [seg1.as_str(), seg2.as_str()];
+        let positions = rabin_karp("ACACACGACGATG", &segments, 4_usize, 127_usize);
+        assert_eq!(positions, vec![vec![0, 2, 4, 7], vec![4, 7]]);
+    }
+
+    #[test]
+    fn rabin_karp_pattern_longer_than_text_test() {
+        let seg = String::from("ACACACGACGATGXYZ");
+        let segments = [seg.as_str()];
+        let positions = rabin_karp("ACACACGACGATG", &segments, 4_usize, 127_usize);
+        assert_eq!(positions, vec![Vec::new()]);
+    }
+
+    #[test]
+    fn rabin_karp_empty_segments_test() {
+        let segments : [&str; 0] = [];
+        let positions = rabin_karp("ACACACGACGATG", &segments, 4_usize, 127_usize);
+        assert!(positions.is_empty());
+    }
+
+    #[test]
+    fn rabin_karp_multibyte_utf8_text_test() {
+        let text = "héllo wörld";
+        let seg = String::from("wörld");
+        let segments = [seg.as_str()];
+        let positions = rabin_karp(text, &segments, 4_usize, 127_usize);
+        assert_eq!(positions, vec![vec![7_usize]]);
+    }
+}
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
-use candle_core::{Result, Tensor};
+use std::path::Path;
+use candle_core::{safetensors, Device, Result, Tensor};
 use candle_nn::{Module};
 
+use crate::example_net::ActivizedLayer;
+
 #[derive(Clone, Debug)]
 pub struct Sequential<T : Module> {
     layers : Vec<T>
@@ -15,6 +18,36 @@ impl<T : Module> Sequential<T> {
 
 }
 
+impl Sequential<ActivizedLayer> {
+    /// Writes every layer's weight (and bias, if any) to a safetensors
+    /// file, named `layer{i}.weight` / `layer{i}.bias` in forward order.
+    pub fn save_weights(&self, path : impl AsRef<Path>) -> Result<()> {
+        let mut tensors : HashMap<String, Tensor> = HashMap::new();
+        for (i, layer) in self.layers.iter().enumerate() {
+            tensors.insert(format!("layer{i}.weight"), layer.weight().clone());
+            if let Some(bias) = layer.bias() {
+                tensors.insert(format!("layer{i}.bias"), bias.clone());
+            }
+        }
+        safetensors::save(&tensors, path)
+    }
+
+    /// Restores weights saved by `save_weights` into a network already
+    /// built with matching layer shapes (e.g. via `ActivizedLayer::new`),
+    /// so a trained model can be reused without retraining.
+    pub fn load_weights(&mut self, path : impl AsRef<Path>, dev : &Device) -> Result<()> {
+        let loaded = safetensors::load(path, dev)?;
+        for (i, layer) in self.layers.iter_mut().enumerate() {
+            let weight = loaded.get(&format!("layer{i}.weight"))
+                .ok_or_else(|| candle_core::Error::Msg(format!("checkpoint is missing layer{i}.weight")))?
+                .clone();
+            let bias = loaded.get(&format!("layer{i}.bias")).cloned();
+            layer.set_weights(weight, bias);
+        }
+        Ok(())
+    }
+}
+
 impl<T : Module> Module for Sequential<T> {
     fn forward(&self, x: &Tensor) -> Result<Tensor>  {
         self.layers.iter()
@@ -102,9 +135,42 @@ mod tests {
     use candle_core::{Device, Result, Tensor, DType};
     use candle_nn::{Linear, Module, VarBuilder, VarMap, linear};
 
+    use crate::example_net::{ActivizedLayer, Initializer, LayerActivation};
     use crate::sequential_layers::{Sequential, LinearSkipConnection};
     //use sequential_layers::{Sequential, LinearSkipConnection};
 
+    #[test]
+    fn test_save_and_load_weights_round_trip() -> Result<()> {
+        let dev = Device::cuda_if_available(0)?;
+
+        let varmap = VarMap::new();
+        let layer1 = ActivizedLayer::new_seeded(2, 3, LayerActivation::Relu, Initializer::KaimingUniform, 1, &varmap, &dev)?;
+        let layer2 = ActivizedLayer::new_seeded(3, 1, LayerActivation::Sigmoid, Initializer::KaimingUniform, 2, &varmap, &dev)?;
+        let trained = Sequential::new(vec!(layer1, layer2))?;
+
+        let path = std::env::temp_dir().join("mlusr_test_save_and_load_weights_round_trip.safetensors");
+        trained.save_weights(&path)?;
+
+        // Fresh network with matching shapes but different (randomly
+        // initialized) weights, so a passing comparison actually proves
+        // `load_weights` overwrote them.
+        let varmap2 = VarMap::new();
+        let layer1b = ActivizedLayer::new_seeded(2, 3, LayerActivation::Relu, Initializer::KaimingUniform, 3, &varmap2, &dev)?;
+        let layer2b = ActivizedLayer::new_seeded(3, 1, LayerActivation::Sigmoid, Initializer::KaimingUniform, 4, &varmap2, &dev)?;
+        let mut reloaded = Sequential::new(vec!(layer1b, layer2b))?;
+        reloaded.load_weights(&path, &dev)?;
+
+        std::fs::remove_file(&path).ok();
+
+        let input = Tensor::new(&[[1f32, 2.], [3., 4.]], &dev)?;
+        let expected = trained.forward(&input)?;
+        let actual = reloaded.forward(&input)?;
+        let maybe_eq = (expected - actual)?.abs()?.sum(0)?.sum(0)?;
+        assert!(maybe_eq.to_scalar::<f32>()? < 1e-3_f32);
+
+        Ok(())
+    }
+
 
     #[test]
     fn test_sequential() -> Result<()> {
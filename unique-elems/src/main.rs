@@ -1,6 +1,19 @@
 use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use rand::prelude::*;
 
+/// Common interface for distinct-element estimators over a data stream:
+/// feed elements one at a time, read back an estimate of the number of
+/// distinct elements seen, and combine partial sketches from parallel
+/// shards. `merge` is fallible since not every sketch can support it
+/// (see `UniquesInAStream`).
+pub trait CardinalitySketch {
+    fn update(&mut self, new_elem : &u64);
+    fn estimate(&self) -> u64;
+    fn merge(&mut self, other : &Self) -> Result<(), String>;
+}
+
 pub struct UniquesInAStream {
     X      : HashSet<u64>,
     rng    : ThreadRng,
@@ -70,15 +83,157 @@ impl UniquesInAStream {
     }
 }
 
+impl CardinalitySketch for UniquesInAStream {
+    fn update(&mut self, new_elem : &u64) {
+        UniquesInAStream::update(self, new_elem)
+    }
+
+    fn estimate(&self) -> u64 {
+        self.to_result()
+    }
+
+    /// The CVM sketch keeps a shrinking random sample of the elements it
+    /// has actually seen, so two partial samples cannot be combined into
+    /// the sample a single pass over both streams would have produced.
+    /// HyperLogLog's registers can be merged; CVM's sample cannot.
+    fn merge(&mut self, _other : &Self) -> Result<(), String> {
+        Err("UniquesInAStream (CVM) sketches cannot be merged, only HyperLogLog can".to_string())
+    }
+}
+
+/// HyperLogLog distinct-element estimator: `m = 2^b` single-byte
+/// registers, each tracking the longest run of leading zeros seen among
+/// the hashes routed to it. Trades CVM's per-element sampling cost for a
+/// fixed `m`-byte footprint and the ability to `merge` shards.
+pub struct HyperLogLog {
+    b : u32,
+    registers : Vec<u8>,
+}
+
+impl HyperLogLog {
+    pub fn new(b : u32) -> Self {
+        assert!((1..=63).contains(&b), "HyperLogLog: b must be in 1..=63, got {b}");
+        HyperLogLog {
+            b,
+            registers : vec![0_u8; 1_usize << b],
+        }
+    }
+
+    fn m(&self) -> usize {
+        self.registers.len()
+    }
+
+    // alpha_m is the standard HyperLogLog bias correction, valid for m >= 128.
+    fn alpha_m(&self) -> f64 {
+        let m = self.m() as f64;
+        0.7213 / (1.0 + 1.079 / m)
+    }
+
+    fn hash(elem : &u64) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        elem.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl CardinalitySketch for HyperLogLog {
+    fn update(&mut self, new_elem : &u64) {
+        let h = Self::hash(new_elem);
+        // top b bits pick the register, the rest feed the leading-zero count.
+        let j = (h >> (64 - self.b)) as usize;
+        let remaining = h << self.b;
+        let rho = (remaining.leading_zeros().min(64 - self.b) + 1) as u8;
+        if rho > self.registers[j] {
+            self.registers[j] = rho;
+        }
+    }
+
+    fn estimate(&self) -> u64 {
+        let m = self.m() as f64;
+        let sum_inv : f64 = self.registers.iter()
+                                .map(|&r| 2_f64.powi(-(r as i32)))
+                                .sum();
+        let mut e = self.alpha_m() * m * m / sum_inv;
+
+        if e <= 2.5 * m {
+            let zero_registers = self.registers.iter().filter(|&&r| r == 0).count();
+            if zero_registers > 0 {
+                e = m * (m / zero_registers as f64).ln();
+            }
+        }
+        e.round() as u64
+    }
+
+    fn merge(&mut self, other : &Self) -> Result<(), String> {
+        if self.registers.len() != other.registers.len() {
+            return Err(format!(
+                "cannot merge HyperLogLog sketches with a different register count ({} vs {})",
+                self.registers.len(), other.registers.len()));
+        }
+        for (r, o) in self.registers.iter_mut().zip(other.registers.iter()) {
+            *r = (*r).max(*o);
+        }
+        Ok(())
+    }
+}
 
 fn main() {
     let example_stream : Vec<u64> = (0.. 100).map(|v| (1+(-1 as i32).pow(v)) as u64).collect();
     let mut uniques = UniquesInAStream::new(0_usize, 0.1, 0.1);
     example_stream.iter().for_each(|v| uniques.update(v));
-    println!("The sequence 0,2,0,2... has 2 distinct elements? Result: {:?}", uniques.to_result());
+    println!("The sequence 2,0,2,0... has 2 distinct elements? CVM result: {:?}", uniques.estimate());
+    let mut hll = HyperLogLog::new(4);
+    example_stream.iter().for_each(|v| hll.update(v));
+    println!("The sequence 2,0,2,0... has 2 distinct elements? HyperLogLog result: {:?}", hll.estimate());
 
     let example_stream : Vec<u64> = (0.. 1000000).collect();
     let mut uniques = UniquesInAStream::new(0_usize, 0.1, 0.1);
     example_stream.iter().for_each(|v| uniques.update(v));
-    println!("The sequence 0,1,... 10^6-1 has 10^6 distinct elements? Result: {:?}", uniques.to_result());
+    println!("The sequence 0,1,... 10^6-1 has 10^6 distinct elements? CVM result: {:?}", uniques.estimate());
+    let mut hll = HyperLogLog::new(14);
+    example_stream.iter().for_each(|v| hll.update(v));
+    println!("The sequence 0,1,... 10^6-1 has 10^6 distinct elements? HyperLogLog result: {:?}", hll.estimate());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hyperloglog_estimate_within_tolerance() {
+        let true_count = 100_000_u64;
+        let mut hll = HyperLogLog::new(10);
+        for v in 0..true_count {
+            hll.update(&v);
+        }
+
+        let estimate = hll.estimate();
+        let error = (estimate as f64 - true_count as f64).abs() / true_count as f64;
+        assert!(error < 0.1, "estimate {estimate} too far from true count {true_count}");
+    }
+
+    #[test]
+    fn test_hyperloglog_merge_combines_disjoint_shards() {
+        let true_count = 20_000_u64;
+        let mut shard_a = HyperLogLog::new(10);
+        for v in 0..true_count / 2 {
+            shard_a.update(&v);
+        }
+        let mut shard_b = HyperLogLog::new(10);
+        for v in true_count / 2..true_count {
+            shard_b.update(&v);
+        }
+
+        shard_a.merge(&shard_b).unwrap();
+        let estimate = shard_a.estimate();
+        let error = (estimate as f64 - true_count as f64).abs() / true_count as f64;
+        assert!(error < 0.1, "merged estimate {estimate} too far from true count {true_count}");
+    }
+
+    #[test]
+    fn test_hyperloglog_merge_rejects_mismatched_register_counts() {
+        let mut small = HyperLogLog::new(4);
+        let large = HyperLogLog::new(10);
+        assert!(small.merge(&large).is_err());
+    }
 }